@@ -1,12 +1,16 @@
 use agvg;
 
 use agvg::bacon::{
-    align_to_preferred_multiple, max_batch_size, prepare_prefixes, Callback, Context,
+    align_to_preferred_multiple, max_batch_size, prepare_prefixes, BenchStatistics, Callback,
+    Context,
 };
 
 use algonaut_crypto;
+use crossbeam_channel;
 use csv;
+use std::collections::HashMap;
 use std::io::BufRead;
+use std::io::Write;
 
 use clap::Parser;
 
@@ -30,6 +34,9 @@ struct OptimizeCommand {
     max: usize,
     #[arg(long, default_value_t = false)]
     ordered: bool,
+    /// find the peak via golden-section search instead of sweeping the whole range
+    #[arg(long, default_value_t = false)]
+    climb: bool,
     #[arg(long, default_value_t = String::from(""))]
     output: String,
     #[arg(long, default_value_t = 0)]
@@ -41,6 +48,11 @@ struct OptimizeCommand {
     cpu: bool,
     #[arg(long, default_value_t = false)]
     all: bool,
+
+    /// append a tab-separated record of every probe to this file, resuming
+    /// the sequence number if the file already exists
+    #[arg(long, default_value_t = String::from(""))]
+    joblog: String,
 }
 
 #[derive(Parser)]
@@ -71,6 +83,25 @@ struct GenerateCommand {
 
     #[arg(long, default_value_t = String::from(""))]
     output: String,
+
+    /// break benchmark timing down into seed-upload/kernel/readback phases per batch
+    #[arg(long, default_value_t = false)]
+    benchmark_detail: bool,
+
+    /// stream found keys to a pool of writer threads instead of decoding them
+    /// inline on the GPU-stepping thread
+    #[arg(long, default_value_t = false)]
+    stream: bool,
+
+    /// append a tab-separated record of every match to this file, resuming
+    /// the sequence number if the file already exists
+    #[arg(long, default_value_t = String::from(""))]
+    joblog: String,
+
+    /// print expected attempts, match probability so far, and an ETA for the
+    /// next match, estimated from the prefixes' combined entropy
+    #[arg(long, default_value_t = false)]
+    progress: bool,
 }
 
 fn read_prefixes_from_file(file: &str, prefixes: &mut Vec<String>) {
@@ -84,6 +115,72 @@ fn read_prefixes_from_file(file: &str, prefixes: &mut Vec<String>) {
     }
 }
 
+/// Appends a tab-separated record for every match or optimize probe.
+struct JobLog {
+    writer: std::io::BufWriter<std::fs::File>,
+    seq: u64,
+    run_start: std::time::Instant,
+    run_start_epoch: u64,
+}
+
+impl JobLog {
+    fn open(path: &str) -> Self {
+        let mut seq = 0u64;
+
+        if let Ok(file) = std::fs::File::open(path) {
+            for line in std::io::BufReader::new(file).lines() {
+                let line = line.unwrap();
+                if let Some(n) = line.split('\t').next().and_then(|s| s.parse::<u64>().ok()) {
+                    seq = seq.max(n);
+                }
+            }
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+
+        JobLog {
+            writer: std::io::BufWriter::new(file),
+            seq,
+            run_start: std::time::Instant::now(),
+            run_start_epoch: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        }
+    }
+
+    fn record(&mut self, batch_size: usize, prefix: &str, address: &str) {
+        self.seq += 1;
+
+        writeln!(
+            self.writer,
+            "{}\t{}\t{:.3}\t{}\t{}\t{}",
+            self.seq,
+            self.run_start_epoch,
+            self.run_start.elapsed().as_secs_f64(),
+            batch_size,
+            prefix,
+            address,
+        )
+        .unwrap();
+        self.writer.flush().unwrap();
+    }
+}
+
+/// Per-attempt match probability; `prefixes` is expected to already be
+/// de-duplicated via `prepare_prefixes`.
+fn prefix_match_probability(prefixes: &[String]) -> f64 {
+    1.0 - prefixes
+        .iter()
+        .filter(|p| !p.is_empty())
+        .map(|p| 1.0 - 32f64.powi(-(p.len() as i32)))
+        .product::<f64>()
+}
+
 struct DummyCallback {}
 
 impl Callback for DummyCallback {
@@ -97,6 +194,9 @@ struct PrintCallback {
     writer: Option<csv::Writer<std::fs::File>>,
     found: usize,
     count: usize,
+    prefixes: Vec<String>,
+    joblog: Option<JobLog>,
+    batch_size: std::sync::Arc<std::sync::atomic::AtomicUsize>,
 }
 
 impl Callback for PrintCallback {
@@ -105,18 +205,32 @@ impl Callback for PrintCallback {
 
         let m = algonaut_crypto::mnemonic::from_key(key).unwrap();
         let acc = algonaut::transaction::account::Account::from_mnemonic(&m).unwrap();
+        let address = acc.address().to_string();
 
         if self.print {
-            println!("{},{}", acc.address(), m);
+            println!("{},{}", address, m);
         }
 
         if let Some(ref mut writer) = self.writer {
-            writer
-                .write_record(&[acc.address().to_string(), m])
-                .unwrap();
+            writer.write_record(&[address.clone(), m]).unwrap();
             writer.flush().unwrap();
         }
 
+        if let Some(ref mut joblog) = self.joblog {
+            let prefix = self
+                .prefixes
+                .iter()
+                .find(|p| !p.is_empty() && address.starts_with(p.as_str()))
+                .cloned()
+                .unwrap_or_default();
+
+            joblog.record(
+                self.batch_size.load(std::sync::atomic::Ordering::Relaxed),
+                &prefix,
+                &address,
+            );
+        }
+
         self.found < self.count
     }
 }
@@ -129,15 +243,90 @@ fn main() {
     }
 }
 
+fn report_progress(
+    args: &GenerateCommand,
+    total: usize,
+    start: std::time::Instant,
+    batch_start: std::time::Instant,
+    batch_size: usize,
+    p_match: f64,
+    expected_attempts: f64,
+) {
+    let now = std::time::Instant::now();
+    let total_elapsed: std::time::Duration = now.duration_since(start);
+    let batch_elapsed: std::time::Duration = now.duration_since(batch_start);
+
+    let performance = total as f64 / total_elapsed.as_secs_f64();
+    let batch_performance = batch_size as f64 / batch_elapsed.as_secs_f64();
+
+    if args.benchmark {
+        println!(
+            "Elapsed: {}.{:03}s, total: {}, avg/s: {}, last/s: {}",
+            total_elapsed.as_secs(),
+            total_elapsed.subsec_millis(),
+            total,
+            performance as usize,
+            batch_performance as usize,
+        );
+    }
+
+    if args.progress {
+        let match_probability = 1.0 - (1.0 - p_match).powf(total as f64);
+        let eta_secs = expected_attempts / performance;
+
+        println!(
+            "Progress: expected attempts {:.0}, P(match so far) {:.4}%, ETA for next match: {:.1}s",
+            expected_attempts,
+            match_probability * 100.0,
+            eta_secs,
+        );
+    }
+}
+
+fn validate_args(args: &GenerateCommand) -> Result<(), String> {
+    if args.stream && args.benchmark_detail {
+        return Err(
+            "--stream does not support --benchmark-detail: per-phase timing needs the inline \
+             step_timed() path, which the channel-based streamed runner doesn't go through. \
+             Drop one of the two flags."
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
 fn generate(args: GenerateCommand) {
+    if let Err(e) = validate_args(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    if args.stream {
+        return generate_streamed(args);
+    }
+
     let ctx = Context::new(args.cpu);
 
     let mut prefixes = vec![args.prefixes];
     read_prefixes_from_file(&args.file, &mut prefixes);
+    let prefixes = prepare_prefixes(&prefixes);
+
+    // --benchmark-detail repurposes --output for the per-batch phase-timing
+    // CSV, since the two flags are meant to be used together for profiling
+    // runs rather than for collecting found keys.
+    let mut bench_writer = if args.benchmark_detail && args.output != "" {
+        let file = std::fs::File::create(&args.output).unwrap();
+        Some(csv::Writer::from_writer(file))
+    } else {
+        None
+    };
+
+    let batch_size_shared = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
     let cb: Box<dyn Callback + Send> = Box::new(PrintCallback {
-        print: args.output == "",
-        writer: if args.output != "" {
+        print: args.output == "" || args.benchmark_detail,
+        writer: if args.output != "" && !args.benchmark_detail {
             let file = std::fs::File::create(args.output).unwrap();
             let writer = csv::Writer::from_writer(file);
             Some(writer)
@@ -146,6 +335,13 @@ fn generate(args: GenerateCommand) {
         },
         found: 0,
         count: args.count,
+        prefixes: prefixes.clone(),
+        joblog: if args.joblog != "" {
+            Some(JobLog::open(&args.joblog))
+        } else {
+            None
+        },
+        batch_size: batch_size_shared.clone(),
     });
 
     let init = ctx.prepare(&prefixes);
@@ -156,31 +352,175 @@ fn generate(args: GenerateCommand) {
             args.worker_concurrency,
             Some(cb),
         );
+        batch_size_shared.store(runner.batch_size(), std::sync::atomic::Ordering::Relaxed);
 
         let start = std::time::Instant::now();
         let mut total = 0;
 
+        let p_match = prefix_match_probability(&prefixes);
+        let expected_attempts = if p_match > 0.0 { 1.0 / p_match } else { f64::INFINITY };
+
         loop {
             let batch_start = std::time::Instant::now();
 
-            let (processed, stop) = runner.step();
+            let (processed, stop) = if args.benchmark_detail {
+                let (stats, stop): (BenchStatistics, bool) = runner.step_timed();
+
+                let phase_total =
+                    stats.seed_upload + stats.kernel_compute + stats.result_readback;
+                let phase_total_secs = phase_total.as_secs_f64();
+
+                if phase_total_secs > 0.0 {
+                    println!(
+                        "Phases: seed_upload {:.1}%, kernel_compute {:.1}%, result_readback {:.1}%",
+                        100.0 * stats.seed_upload.as_secs_f64() / phase_total_secs,
+                        100.0 * stats.kernel_compute.as_secs_f64() / phase_total_secs,
+                        100.0 * stats.result_readback.as_secs_f64() / phase_total_secs,
+                    );
+                }
+
+                if let Some(ref mut writer) = bench_writer {
+                    writer
+                        .write_record(&[
+                            stats.batch_size.to_string(),
+                            stats.processed.to_string(),
+                            stats.seed_upload.as_secs_f64().to_string(),
+                            stats.kernel_compute.as_secs_f64().to_string(),
+                            stats.result_readback.as_secs_f64().to_string(),
+                        ])
+                        .unwrap();
+                    writer.flush().unwrap();
+                }
+
+                (stats.processed, stop)
+            } else {
+                runner.step()
+            };
             total += processed;
 
-            if args.benchmark && !stop && total > 0 {
-                let now = std::time::Instant::now();
-                let total_elapsed: std::time::Duration = now.duration_since(start);
-                let batch_elapsed: std::time::Duration = now.duration_since(batch_start);
+            if (args.benchmark || args.progress) && !stop && total > 0 {
+                report_progress(
+                    &args,
+                    total,
+                    start,
+                    batch_start,
+                    runner.batch_size(),
+                    p_match,
+                    expected_attempts,
+                );
+            }
 
-                let performance = total as f64 / total_elapsed.as_secs_f64();
-                let batch_performance = runner.batch_size() as f64 / batch_elapsed.as_secs_f64();
+            if stop {
+                break;
+            }
+        }
+    }
+}
 
-                println!(
-                    "Elapsed: {}.{:03}s, total: {}, avg/s: {}, last/s: {}",
-                    total_elapsed.as_secs(),
-                    total_elapsed.subsec_millis(),
+/// Same as `generate`, but found keys are pushed onto a bounded channel and
+/// decoded/written by a pool of consumer threads instead of inline on the
+/// GPU-stepping thread, so a slow disk or CSV flush no longer stalls stepping.
+fn generate_streamed(args: GenerateCommand) {
+    let ctx = Context::new(args.cpu);
+
+    let mut prefixes = vec![args.prefixes];
+    read_prefixes_from_file(&args.file, &mut prefixes);
+    let prefixes = prepare_prefixes(&prefixes);
+
+    let print = args.output == "";
+    let shared_writer = if !print {
+        let file = std::fs::File::create(&args.output).unwrap();
+        Some(std::sync::Arc::new(std::sync::Mutex::new(
+            csv::Writer::from_writer(file),
+        )))
+    } else {
+        None
+    };
+
+    let writer_threads = std::cmp::max(args.worker_concurrency, 1);
+
+    let shared_joblog = if args.joblog != "" {
+        Some(std::sync::Arc::new(std::sync::Mutex::new(JobLog::open(
+            &args.joblog,
+        ))))
+    } else {
+        None
+    };
+    let batch_size_shared = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+    let init = ctx.prepare(&prefixes);
+    unsafe {
+        let (mut runner, rx): (_, crossbeam_channel::Receiver<Vec<u8>>) = init.prepare_streamed(
+            args.batch,
+            args.seed_concurrency,
+            args.worker_concurrency,
+            args.count,
+        );
+        batch_size_shared.store(runner.batch_size(), std::sync::atomic::Ordering::Relaxed);
+
+        let handles: Vec<_> = (0..writer_threads)
+            .map(|_| {
+                let rx = rx.clone();
+                let shared_writer = shared_writer.clone();
+                let shared_joblog = shared_joblog.clone();
+                let prefixes = prefixes.clone();
+                let batch_size_shared = batch_size_shared.clone();
+
+                std::thread::spawn(move || {
+                    for key in rx {
+                        let m = algonaut_crypto::mnemonic::from_key(&key).unwrap();
+                        let acc = algonaut::transaction::account::Account::from_mnemonic(&m).unwrap();
+                        let address = acc.address().to_string();
+
+                        if print {
+                            println!("{},{}", address, m);
+                        }
+
+                        if let Some(ref writer) = shared_writer {
+                            let mut writer = writer.lock().unwrap();
+                            writer.write_record(&[address.clone(), m]).unwrap();
+                            writer.flush().unwrap();
+                        }
+
+                        if let Some(ref joblog) = shared_joblog {
+                            let prefix = prefixes
+                                .iter()
+                                .find(|p| !p.is_empty() && address.starts_with(p.as_str()))
+                                .cloned()
+                                .unwrap_or_default();
+
+                            joblog.lock().unwrap().record(
+                                batch_size_shared.load(std::sync::atomic::Ordering::Relaxed),
+                                &prefix,
+                                &address,
+                            );
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let start = std::time::Instant::now();
+        let mut total = 0;
+
+        let p_match = prefix_match_probability(&prefixes);
+        let expected_attempts = if p_match > 0.0 { 1.0 / p_match } else { f64::INFINITY };
+
+        loop {
+            let batch_start = std::time::Instant::now();
+
+            let (processed, stop) = runner.step();
+            total += processed;
+
+            if (args.benchmark || args.progress) && !stop && total > 0 {
+                report_progress(
+                    &args,
                     total,
-                    performance as usize,
-                    batch_performance as usize,
+                    start,
+                    batch_start,
+                    runner.batch_size(),
+                    p_match,
+                    expected_attempts,
                 );
             }
 
@@ -188,6 +528,13 @@ fn generate(args: GenerateCommand) {
                 break;
             }
         }
+
+        drop(runner);
+        drop(rx);
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
     }
 }
 
@@ -229,94 +576,227 @@ fn optimize(args: OptimizeCommand) {
         output_path => Some(csv::WriterBuilder::new().from_path(output_path).unwrap()),
     };
 
+    let mut joblog = if args.joblog != "" {
+        Some(JobLog::open(&args.joblog))
+    } else {
+        None
+    };
+    let joblog_prefixes = prefixes.join(",");
+
     let init = ctx.prepare(&prefixes);
 
-    unsafe {
-        loop {
-            if !args.ordered {
-                let rnd = rand::random::<usize>();
-                let val = match to_batch_size - from_batch_size {
-                    0 => 0,
-                    x => rnd % x,
-                };
-
-                current_batch_size =
-                    align_to_preferred_multiple(val + from_batch_size, preferred_multiple);
+    if args.climb {
+        let mut cache: HashMap<usize, f64> = HashMap::new();
+
+        let mut measure = |batch_size: usize| -> f64 {
+            if let Some(&performance) = cache.get(&batch_size) {
+                return performance;
             }
 
-            let mut runner = init.prepare(
-                current_batch_size,
-                args.seed_concurrency,
-                args.worker_concurrency,
-                None,
-            );
+            let performance = unsafe {
+                let mut runner = init.prepare(
+                    batch_size,
+                    args.seed_concurrency,
+                    args.worker_concurrency,
+                    None,
+                );
+
+                let mut total = 0;
+
+                {
+                    let mut preheat_processed = 0;
+
+                    loop {
+                        let (processed, _) = runner.step();
 
-            let mut total = 0;
+                        preheat_processed += processed;
+                        if preheat_processed > runner.batch_size() * 2 {
+                            break;
+                        }
+                    }
+                }
 
-            {
-                let mut preheat_processed = 0;
+                let start = std::time::Instant::now();
 
                 loop {
                     let (processed, _) = runner.step();
+                    total += processed;
 
-                    preheat_processed += processed;
-                    if preheat_processed > runner.batch_size() * 2 {
-                        break;
+                    let elapsed = start.elapsed();
+                    if elapsed.is_zero() {
+                        continue;
+                    }
+
+                    let performance = total as f64 / elapsed.as_secs_f64();
+
+                    if performance as usize > 0 && total as f64 >= performance {
+                        break performance;
                     }
                 }
+            };
+
+            if performance > best_performance {
+                best_batch_size = batch_size;
+                best_performance = performance;
+
+                println!(
+                    "Best batch size: {}, performance: {}",
+                    best_batch_size, best_performance as usize
+                );
+            } else if args.all {
+                println!(
+                    "Batch size: {}, performance: {}",
+                    batch_size, performance as usize
+                );
             }
 
-            let start = std::time::Instant::now();
+            match f {
+                Some(ref mut f) => {
+                    f.write_record(&[batch_size.to_string(), (performance as usize).to_string()])
+                        .unwrap();
+                    f.flush().unwrap();
+                }
+                _ => {}
+            }
 
+            if let Some(ref mut joblog) = joblog {
+                joblog.record(batch_size, &joblog_prefixes, "");
+            }
+
+            cache.insert(batch_size, performance);
+            performance
+        };
+
+        // golden-section search over [lo, hi]
+        const PHI: f64 = 0.618_033_988_749_895;
+
+        let mut lo = from_batch_size;
+        let mut hi = to_batch_size;
+
+        let mut x1 = align_to_preferred_multiple(
+            lo + ((hi - lo) as f64 * (1.0 - PHI)) as usize,
+            preferred_multiple,
+        );
+        let mut x2 = align_to_preferred_multiple(
+            lo + ((hi - lo) as f64 * PHI) as usize,
+            preferred_multiple,
+        );
+        let mut f1 = measure(x1);
+        let mut f2 = measure(x2);
+
+        while hi > lo && hi - lo > preferred_multiple {
+            if f1 < f2 {
+                lo = x1;
+                x1 = x2;
+                f1 = f2;
+                x2 = align_to_preferred_multiple(
+                    lo + ((hi - lo) as f64 * PHI) as usize,
+                    preferred_multiple,
+                );
+                f2 = measure(x2);
+            } else {
+                hi = x2;
+                x2 = x1;
+                f2 = f1;
+                x1 = align_to_preferred_multiple(
+                    lo + ((hi - lo) as f64 * (1.0 - PHI)) as usize,
+                    preferred_multiple,
+                );
+                f1 = measure(x1);
+            }
+        }
+    } else {
+        unsafe {
             loop {
-                let (processed, _) = runner.step();
-                total += processed;
+                if !args.ordered {
+                    let rnd = rand::random::<usize>();
+                    let val = match to_batch_size - from_batch_size {
+                        0 => 0,
+                        x => rnd % x,
+                    };
+
+                    current_batch_size =
+                        align_to_preferred_multiple(val + from_batch_size, preferred_multiple);
+                }
 
-                let elapsed = start.elapsed();
-                if elapsed.is_zero() {
-                    continue;
+                let mut runner = init.prepare(
+                    current_batch_size,
+                    args.seed_concurrency,
+                    args.worker_concurrency,
+                    None,
+                );
+
+                let mut total = 0;
+
+                {
+                    let mut preheat_processed = 0;
+
+                    loop {
+                        let (processed, _) = runner.step();
+
+                        preheat_processed += processed;
+                        if preheat_processed > runner.batch_size() * 2 {
+                            break;
+                        }
+                    }
                 }
 
-                let performance = total as f64 / elapsed.as_secs_f64();
+                let start = std::time::Instant::now();
 
-                if performance as usize > 0 && total as f64 >= performance {
-                    if performance > best_performance {
-                        best_batch_size = current_batch_size;
-                        best_performance = performance;
+                loop {
+                    let (processed, _) = runner.step();
+                    total += processed;
+
+                    let elapsed = start.elapsed();
+                    if elapsed.is_zero() {
+                        continue;
+                    }
+
+                    let performance = total as f64 / elapsed.as_secs_f64();
+
+                    if performance as usize > 0 && total as f64 >= performance {
+                        if performance > best_performance {
+                            best_batch_size = current_batch_size;
+                            best_performance = performance;
 
-                        println!(
-                            "Best batch size: {}, performance: {}",
-                            best_batch_size, best_performance as usize
-                        );
-                    } else {
-                        if args.all {
                             println!(
-                                "Batch size: {}, performance: {}",
-                                current_batch_size, performance as usize
+                                "Best batch size: {}, performance: {}",
+                                best_batch_size, best_performance as usize
                             );
+                        } else {
+                            if args.all {
+                                println!(
+                                    "Batch size: {}, performance: {}",
+                                    current_batch_size, performance as usize
+                                );
+                            }
                         }
-                    }
 
-                    match f {
-                        Some(ref mut f) => {
-                            f.write_record(&[
-                                current_batch_size.to_string(),
-                                (performance as usize).to_string(),
-                            ])
-                            .unwrap();
-                            f.flush().unwrap();
+                        match f {
+                            Some(ref mut f) => {
+                                f.write_record(&[
+                                    current_batch_size.to_string(),
+                                    (performance as usize).to_string(),
+                                ])
+                                .unwrap();
+                                f.flush().unwrap();
+                            }
+                            _ => {}
                         }
-                        _ => {}
-                    }
 
-                    break;
+                        if let Some(ref mut joblog) = joblog {
+                            joblog.record(current_batch_size, &joblog_prefixes, "");
+                        }
+
+                        break;
+                    }
                 }
-            }
 
-            if args.ordered {
-                current_batch_size += preferred_multiple;
-                if current_batch_size > to_batch_size {
-                    break;
+                if args.ordered {
+                    current_batch_size += preferred_multiple;
+                    if current_batch_size > to_batch_size {
+                        break;
+                    }
                 }
             }
         }
@@ -332,6 +812,105 @@ fn optimize(args: OptimizeCommand) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_prefix_match_probability_single_prefix() {
+        let p = prefix_match_probability(&["A".to_string()]);
+        assert!((p - 1.0 / 32.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_prefix_match_probability_unions_distinct_prefixes() {
+        let p = prefix_match_probability(&["AB".to_string(), "CD".to_string()]);
+        let each = 32f64.powi(-2);
+        let expected = 1.0 - (1.0 - each) * (1.0 - each);
+        assert!((p - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_prefix_match_probability_empty_is_zero() {
+        assert_eq!(prefix_match_probability(&[]), 0.0);
+        assert_eq!(prefix_match_probability(&["".to_string()]), 0.0);
+    }
+
+    #[test]
+    fn test_joblog_resumes_sequence_from_existing_file() {
+        let path = std::env::temp_dir().join(format!("bacon_joblog_resume_{}.tsv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        std::fs::write(&path, "1\t0\t0.000\t32\tAA\taddr1\n2\t0\t0.001\t32\tAA\taddr2\n").unwrap();
+
+        let mut joblog = JobLog::open(&path);
+        joblog.record(32, "AA", "addr3");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let last_line = contents.lines().last().unwrap();
+        let seq: u64 = last_line.split('\t').next().unwrap().parse().unwrap();
+        assert_eq!(seq, 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_joblog_starts_at_one_for_missing_file() {
+        let path = std::env::temp_dir().join(format!("bacon_joblog_new_{}.tsv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let mut joblog = JobLog::open(&path);
+        joblog.record(32, "AA", "addr1");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let seq: u64 = contents
+            .lines()
+            .next()
+            .unwrap()
+            .split('\t')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(seq, 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn default_generate_args() -> GenerateCommand {
+        GenerateCommand {
+            prefixes: "".to_string(),
+            file: "".to_string(),
+            batch: 0,
+            seed_concurrency: 0,
+            worker_concurrency: 0,
+            count: 1,
+            benchmark: false,
+            cpu: false,
+            output: "".to_string(),
+            benchmark_detail: false,
+            stream: false,
+            joblog: "".to_string(),
+            progress: false,
+        }
+    }
+
+    #[test]
+    fn test_validate_args_rejects_stream_with_benchmark_detail() {
+        let args = GenerateCommand {
+            stream: true,
+            benchmark_detail: true,
+            ..default_generate_args()
+        };
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_validate_args_allows_stream_without_benchmark_detail() {
+        let args = GenerateCommand {
+            stream: true,
+            ..default_generate_args()
+        };
+        assert!(validate_args(&args).is_ok());
+    }
+
     #[test]
     fn test_optimize() {
         let multiple = {
@@ -345,13 +924,38 @@ mod tests {
             min: multiple,
             max: multiple,
             ordered: true,
+            climb: false,
             output: "".to_string(),
             seed_concurrency: 0,
             worker_concurrency: 0,
             cpu: false,
             all: false,
+            joblog: "".to_string(),
         });
     }
+    #[test]
+    fn test_optimize_climb() {
+        let multiple = {
+            let ctx = Context::new(false);
+            ctx.preferred_multiple()
+        };
+
+        optimize(OptimizeCommand {
+            prefixes: "".to_string(),
+            file: "".to_string(),
+            min: multiple,
+            max: multiple * 4,
+            ordered: true,
+            climb: true,
+            output: "".to_string(),
+            seed_concurrency: 0,
+            worker_concurrency: 0,
+            cpu: false,
+            all: false,
+            joblog: "".to_string(),
+        });
+    }
+
     #[test]
     fn test_generate() {
         let ctx = Context::new(false);
@@ -366,4 +970,17 @@ mod tests {
             assert_eq!(second.1, false);
         }
     }
+
+    #[test]
+    fn test_generate_step_timed() {
+        let ctx = Context::new(false);
+        let init = ctx.prepare(&vec!["A".to_string()]);
+
+        unsafe {
+            let mut runner = init.prepare(32, 2, 2, None);
+            let (stats, stop): (BenchStatistics, bool) = runner.step_timed();
+            assert_eq!(stats.batch_size, runner.batch_size());
+            assert_eq!(stop, false);
+        }
+    }
 }
\ No newline at end of file